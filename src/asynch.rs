@@ -0,0 +1,230 @@
+//! `embedded-io-async` support for [`crate::PioUartRx`]/[`crate::PioUartTx`],
+//! backed by the PIO state machine's RX-not-empty/TX-not-full interrupt
+//! flags instead of polling the FIFOs from a busy loop.
+//!
+//! Mirrors how `embassy-rp`'s PIO UART driver parks tasks on the FIFO
+//! interrupt: a future registers its waker in a fixed-size per-SM table and
+//! returns `Pending`, and the PIO IRQ handler wakes it once the condition
+//! it's waiting on (FIFO no longer empty/full) holds.
+//!
+//! `rp2040_hal`'s `pio::PIO` wrapper only exposes the `irq` instruction's
+//! four SM flag interrupts, not the RXNEMPTY/TXNFULL FIFO interrupts also
+//! routed to `PIOx_IRQ_0` -- and by the time a [`PioUartRx`]/[`PioUartTx`]
+//! exists it no longer holds a `PIO` instance to ask anyway (it was
+//! consumed by `split()` back when the state machines were built). So this
+//! module pokes `IRQ0_INTE`/`IRQ0_INTS` directly from the fixed PIO0/PIO1
+//! peripheral base instead, the same way the rest of this crate derives
+//! addresses from just the `PIO`/`SM` types (e.g. `pio_irq_ptr` in
+//! `lib.rs`).
+
+use core::cell::RefCell;
+use core::future::poll_fn;
+use core::marker::PhantomData;
+use core::task::{Poll, Waker};
+
+use critical_section::Mutex;
+use rp2040_hal::pio::{self, PIOExt, StateMachineIndex};
+
+use crate::{PioSerialError, PioUartRx, PioUartTx};
+
+const NUM_PIO: usize = 2;
+const NUM_SM: usize = 4;
+const NUM_SLOTS: usize = NUM_PIO * NUM_SM;
+
+// One waker slot per (PIO instance, state machine), for RX and TX separately.
+static RX_WAKERS: [Mutex<RefCell<Option<Waker>>>; NUM_SLOTS] =
+    [const { Mutex::new(RefCell::new(None)) }; NUM_SLOTS];
+static TX_WAKERS: [Mutex<RefCell<Option<Waker>>>; NUM_SLOTS] =
+    [const { Mutex::new(RefCell::new(None)) }; NUM_SLOTS];
+
+fn slot<PIO: PIOExt, SM: StateMachineIndex>() -> usize {
+    PIO::id() as usize * NUM_SM + SM::id() as usize
+}
+
+fn register_waker(table: &[Mutex<RefCell<Option<Waker>>>; NUM_SLOTS], slot: usize, waker: &Waker) {
+    critical_section::with(|cs| {
+        table[slot].borrow(cs).replace(Some(waker.clone()));
+    });
+}
+
+fn wake(table: &[Mutex<RefCell<Option<Waker>>>; NUM_SLOTS], slot: usize) {
+    critical_section::with(|cs| {
+        if let Some(waker) = table[slot].borrow(cs).borrow_mut().take() {
+            waker.wake();
+        }
+    });
+}
+
+// RP2040 datasheet 3.7: `PIOx_IRQ_0` is driven by `IRQ0_INTE`/`IRQ0_INTS`, a
+// 12-bit register per PIO block where bit `sm` is RXNEMPTY, `sm + 4` is
+// TXNFULL, and `sm + 8` is that SM's `irq` instruction flag.
+const PIO0_BASE: u32 = 0x5020_0000;
+const PIO1_BASE: u32 = 0x5030_0000;
+const IRQ0_INTE_OFFSET: u32 = 0x12c;
+const IRQ0_INTS_OFFSET: u32 = 0x134;
+
+fn irq0_inte_ptr<PIO: PIOExt>() -> *mut u32 {
+    let base = if PIO::id() == 0 { PIO0_BASE } else { PIO1_BASE };
+    (base + IRQ0_INTE_OFFSET) as *mut u32
+}
+
+fn irq0_ints_ptr<PIO: PIOExt>() -> *const u32 {
+    let base = if PIO::id() == 0 { PIO0_BASE } else { PIO1_BASE };
+    (base + IRQ0_INTS_OFFSET) as *const u32
+}
+
+fn set_inte_bit<PIO: PIOExt>(bit: u8, enable: bool) {
+    let inte = irq0_inte_ptr::<PIO>();
+    critical_section::with(|_| unsafe {
+        let mut v = core::ptr::read_volatile(inte);
+        if enable {
+            v |= 1 << bit;
+        } else {
+            v &= !(1 << bit);
+        }
+        core::ptr::write_volatile(inte, v);
+    });
+}
+
+fn ints_bit_set<PIO: PIOExt>(bit: u8) -> bool {
+    unsafe { core::ptr::read_volatile(irq0_ints_ptr::<PIO>()) & (1 << bit) != 0 }
+}
+
+pub(crate) fn enable_rx_not_empty_interrupt<PIO: PIOExt>(sm: u8) {
+    set_inte_bit::<PIO>(sm, true);
+}
+
+pub(crate) fn enable_tx_not_full_interrupt<PIO: PIOExt>(sm: u8) {
+    set_inte_bit::<PIO>(sm + 4, true);
+}
+
+fn disable_rx_not_empty_interrupt<PIO: PIOExt>(sm: u8) {
+    set_inte_bit::<PIO>(sm, false);
+}
+
+fn disable_tx_not_full_interrupt<PIO: PIOExt>(sm: u8) {
+    set_inte_bit::<PIO>(sm + 4, false);
+}
+
+/// Proof that the caller has wired `PIOx_IRQ_0`'s interrupt handler to call
+/// [`on_pio_irq0::<PIO>`] and unmasked it in the NVIC.
+///
+/// Obtained with the `unsafe` [`Self::acquire`], mirroring how the rest of
+/// this crate's `unsafe` is confined to hardware invariants the type system
+/// can't express (see the `share()` calls in `build_rx`/`build_tx`).
+pub struct PioIrq0Token<PIO: PIOExt> {
+    _pio: PhantomData<PIO>,
+}
+
+impl<PIO: PIOExt> PioIrq0Token<PIO> {
+    /// Creates the token.
+    ///
+    /// # Safety
+    /// The caller must ensure `PIOx_IRQ_0`'s interrupt handler calls
+    /// [`on_pio_irq0::<PIO>`] on every firing, and that the NVIC interrupt
+    /// for it is unmasked, for as long as any async RX/TX endpoint
+    /// obtained with this token is in use.
+    pub unsafe fn acquire() -> Self {
+        Self { _pio: PhantomData }
+    }
+}
+
+/// Call this from the `PIOx_IRQ_0` interrupt handler for `PIO` (e.g.
+/// `PIO0_IRQ_0`/`PIO1_IRQ_0`). Wakes any async RX/TX endpoint whose FIFO
+/// condition is now satisfied, and clears that source's interrupt enable
+/// bit (the corresponding `poll` re-arms it if it's still not ready).
+pub fn on_pio_irq0<PIO: PIOExt>() {
+    for sm in 0..NUM_SM as u8 {
+        let slot = PIO::id() as usize * NUM_SM + sm as usize;
+        if ints_bit_set::<PIO>(sm) {
+            disable_rx_not_empty_interrupt::<PIO>(sm);
+            wake(&RX_WAKERS, slot);
+        }
+        if ints_bit_set::<PIO>(sm + 4) {
+            disable_tx_not_full_interrupt::<PIO>(sm);
+            wake(&TX_WAKERS, slot);
+        }
+    }
+}
+
+/// An async RX endpoint backed by the PIO RX-not-empty interrupt.
+///
+/// Obtained from [`PioUartRx::into_async`].
+pub struct PioUartRxAsync<PinID: rp2040_hal::gpio::PinId, PIO: PIOExt, SM: StateMachineIndex> {
+    pub(crate) inner: PioUartRx<PinID, PIO, SM, pio::Running>,
+}
+
+impl<PinID: rp2040_hal::gpio::PinId, PIO: PIOExt, SM: StateMachineIndex> embedded_io::ErrorType
+    for PioUartRxAsync<PinID, PIO, SM>
+{
+    type Error = PioSerialError;
+}
+
+impl<PinID: rp2040_hal::gpio::PinId, PIO: PIOExt, SM: StateMachineIndex> embedded_io_async::Read
+    for PioUartRxAsync<PinID, PIO, SM>
+{
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        poll_fn(|cx| match self.inner.read_raw(buf) {
+            Ok(0) => {
+                register_waker(&RX_WAKERS, slot::<PIO, SM>(), cx.waker());
+                self.inner.arm_rx_not_empty_interrupt();
+                Poll::Pending
+            }
+            Ok(n) => Poll::Ready(Ok(n)),
+            Err(e) => Poll::Ready(Err(e)),
+        })
+        .await
+    }
+}
+
+/// An async TX endpoint backed by the PIO TX-not-full interrupt.
+///
+/// Obtained from [`PioUartTx::into_async`].
+pub struct PioUartTxAsync<PinID: rp2040_hal::gpio::PinId, PIO: PIOExt, SM: StateMachineIndex> {
+    pub(crate) inner: PioUartTx<PinID, PIO, SM, pio::Running>,
+}
+
+impl<PinID: rp2040_hal::gpio::PinId, PIO: PIOExt, SM: StateMachineIndex> embedded_io::ErrorType
+    for PioUartTxAsync<PinID, PIO, SM>
+{
+    type Error = PioSerialError;
+}
+
+impl<PinID: rp2040_hal::gpio::PinId, PIO: PIOExt, SM: StateMachineIndex> embedded_io_async::Write
+    for PioUartTxAsync<PinID, PIO, SM>
+{
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        // Unlike `PioUartTx::write_raw`, this can't just pass each word to
+        // `write_words` -- that busy-spins on `tx.is_full()` for every
+        // word beyond what currently fits in the FIFO, which would
+        // block the executor exactly like the blocking API this exists to
+        // replace. Instead, write words one at a time with `try_write_word`
+        // and stop (returning a short `Ok`, per the `embedded_io_async`
+        // contract) the moment the FIFO is full, parking on the TX-not-full
+        // interrupt only if nothing could be written at all.
+        let word_count = buf.len() / 2;
+        poll_fn(|cx| {
+            let mut n = 0;
+            while n < word_count {
+                let word = u16::from_le_bytes([buf[(n * 2) + 1], buf[n * 2] & 0x01]);
+                if !self.inner.try_write_word(word) {
+                    break;
+                }
+                n += 1;
+            }
+            if n == 0 && word_count > 0 {
+                register_waker(&TX_WAKERS, slot::<PIO, SM>(), cx.waker());
+                self.inner.arm_tx_not_full_interrupt();
+                return Poll::Pending;
+            }
+            Poll::Ready(Ok(n * 2))
+        })
+        .await
+    }
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        // The FIFO-not-full interrupt only tells us there's room for more,
+        // not that the FIFO has fully drained, so flushing still polls.
+        self.inner.flush();
+        Ok(())
+    }
+}
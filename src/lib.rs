@@ -6,8 +6,12 @@
 //! ## Features
 //! - UART communication using PIO
 //! - Flexible pin assignment for RX and TX
+//! - Configurable frame format: 5-9 data bits, optional parity, 1 or 2 stop bits
 //! - Customizable baud rate and system frequency settings
 //! - Non-blocking read and write operations
+//! - Non-blocking DMA transfers via [`PioUartRx::read_dma`]/[`PioUartTx::write_dma`]
+//! - `embedded-io-async` support via [`PioUartRx::into_async`]/[`PioUartTx::into_async`]
+//! - Half-duplex RS-485 with an automatic driver-enable pin via [`PioUartTx::new_rs485`]
 //!
 //! ## Usage
 //! To use this crate, ensure that you have `rp2040_hal` and `embedded-hal` as dependencies in your `Cargo.toml`.
@@ -15,7 +19,7 @@
 //!
 //! ## Example
 //! ```
-//! use pio_uart::PioUart;
+//! use pio_uart::{PioUart, PioUartConfig};
 //! use embedded_io::{Read, Write};
 //! use fugit::ExtU32;
 //!
@@ -37,6 +41,7 @@
 //!             pins.gpio16.reconfigure(),
 //!             pins.gpio17.reconfigure(),
 //!             &mut pac.RESETS,
+//!             PioUartConfig::default(),
 //!             19200.Hz(),
 //!             125.MHz(),
 //!         );
@@ -50,14 +55,98 @@
 #![no_std]
 #![deny(missing_docs)]
 
+mod asynch;
+pub use asynch::{on_pio_irq0, PioIrq0Token, PioUartRxAsync, PioUartTxAsync};
+
 use rp2040_hal::{
+    dma::{single_buffer, ReadTarget, SingleChannel, WriteTarget},
     gpio::{Pin, PinId, PullNone, PullUp},
     pio::{
-        self, InstallError, InstalledProgram, PIOBuilder, PIOExt, ShiftDirection, StateMachine,
-        StateMachineIndex, UninitStateMachine,
+        self, InstallError, InstalledProgram, Instruction, InstructionOperands, PIOBuilder, PIOExt,
+        SetDestination, ShiftDirection, StateMachine, StateMachineIndex, UninitStateMachine,
     },
 };
 
+/// Parity mode for a [`PioUartConfig`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Parity {
+    /// No parity bit is sent or expected.
+    None,
+    /// An even parity bit follows the data bits.
+    Even,
+    /// An odd parity bit follows the data bits.
+    Odd,
+}
+
+/// Computes the parity bit for `data_bits` of `data` under `parity`,
+/// returned in bit 0. Shared by [`parity_ok`] (checking a
+/// received frame) and [`PioUartTx::write_words`]/
+/// [`PioUartTxRs485::write_words`] (generating one to send), so the
+/// computation only has to be right in one place.
+fn parity_bit(data: u16, parity: Parity) -> u16 {
+    match parity {
+        Parity::None => 0,
+        Parity::Even => data.count_ones() as u16 % 2,
+        Parity::Odd => (data.count_ones() as u16 % 2) ^ 1,
+    }
+}
+
+/// Number of stop bits for a [`PioUartConfig`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopBits {
+    /// One stop bit.
+    One,
+    /// Two stop bits.
+    Two,
+}
+
+/// Frame configuration for a PIO UART: data bits, parity, and stop bits.
+///
+/// The default matches this crate's original fixed framing: 9 data bits,
+/// no parity, one stop bit.
+///
+/// # Fields
+/// - `data_bits`: Number of data bits per frame, `5..=9`.
+/// - `parity`: Parity mode.
+/// - `stop_bits`: Number of stop bits.
+#[derive(Clone, Copy, Debug)]
+pub struct PioUartConfig {
+    /// Number of data bits per frame, `5..=9`.
+    pub data_bits: u8,
+    /// Parity mode.
+    pub parity: Parity,
+    /// Number of stop bits.
+    pub stop_bits: StopBits,
+}
+
+impl PioUartConfig {
+    /// Creates a new [`PioUartConfig`].
+    ///
+    /// # Panics
+    /// Panics if `data_bits` is not in `5..=9`.
+    pub fn new(data_bits: u8, parity: Parity, stop_bits: StopBits) -> Self {
+        assert!((5..=9).contains(&data_bits), "data_bits must be in 5..=9");
+        Self {
+            data_bits,
+            parity,
+            stop_bits,
+        }
+    }
+
+    /// The number of bits shifted per frame, i.e. `data_bits` plus one more
+    /// if `parity` is not [`Parity::None`].
+    fn frame_bits(&self) -> u8 {
+        self.data_bits + if self.parity == Parity::None { 0 } else { 1 }
+    }
+}
+
+impl Default for PioUartConfig {
+    /// `9N1`: 9 data bits, no parity, 1 stop bit -- this crate's original framing.
+    fn default() -> Self {
+        Self::new(9, Parity::None, StopBits::One)
+    }
+}
+
 /// Install the UART Rx program in a PIO instance
 pub fn install_rx_program<PIO: PIOExt>(
     pio: &mut pio::PIO<PIO>,
@@ -66,14 +155,41 @@ pub fn install_rx_program<PIO: PIOExt>(
     let program = program_with_defines.program;
     pio.install(&program).map(|program| RxProgram { program })
 }
-/// Install the UART Tx program in a PIO instance
+/// Install the UART Tx program in a PIO instance.
+///
+/// The assembled variant depends on `stop_bits`: 1 and 2 stop-bit framing
+/// are two small, separately-installed programs (`uart_tx_1sb`/
+/// `uart_tx_2sb` in `uart_tx.pio`) rather than a single program with a
+/// runtime-loaded stop-bit counter, unlike `data_bits`/`parity`, which
+/// are cheap to handle with a runtime-loaded loop count (see
+/// [`PioUartTx::build_tx`]) -- `X`/`Y` are already spoken for by that
+/// loop count, leaving no free register for a second one.
 pub fn install_tx_program<PIO: PIOExt>(
     pio: &mut pio::PIO<PIO>,
+    stop_bits: StopBits,
 ) -> Result<TxProgram<PIO>, InstallError> {
-    let program_with_defines = pio_proc::pio_file!("src/uart_tx.pio",);
+    let program_with_defines = match stop_bits {
+        StopBits::One => pio_proc::pio_file!("src/uart_tx.pio", select_program("uart_tx_1sb")),
+        StopBits::Two => pio_proc::pio_file!("src/uart_tx.pio", select_program("uart_tx_2sb")),
+    };
     let program = program_with_defines.program;
     pio.install(&program).map(|program| TxProgram { program })
 }
+/// Install the half-duplex RS-485 UART Tx program (see [`PioUartTx::new_rs485`])
+/// in a PIO instance.
+///
+/// Unlike [`install_tx_program`], there's only one variant: it assembles a
+/// single stop bit, so [`PioUartTx::new_rs485`] requires `config.stop_bits`
+/// to be [`StopBits::One`].
+pub fn install_tx_rs485_program<PIO: PIOExt>(
+    pio: &mut pio::PIO<PIO>,
+) -> Result<TxRs485Program<PIO>, InstallError> {
+    let program_with_defines =
+        pio_proc::pio_file!("src/uart_tx.pio", select_program("uart_tx_rs485"));
+    let program = program_with_defines.program;
+    pio.install(&program)
+        .map(|program| TxRs485Program { program })
+}
 
 /// Represents a UART interface using the RP2040's PIO hardware.
 ///
@@ -102,10 +218,34 @@ pub struct PioUart<RXID: PinId, TXID: PinId, PIO: PIOExt, State> {
 pub struct PioUartRx<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex, State> {
     rx: pio::Rx<(PIO, SM)>,
     sm: StateMachine<(PIO, SM), State>,
+    config: PioUartConfig,
+    address_filter: Option<AddressFilter>,
+    // Set by `read_words` when it stops draining the FIFO early because of
+    // an overrun or a line/parity error, so the error is reported on the
+    // next call instead of discarding the words already drained into `buf`
+    // on this one.
+    pending_error: Option<PioSerialError>,
     // The following fields are use to restore the original state in `free()`
     _rx_pin: Pin<PinID, PIO::PinFunction, PullUp>,
     _tx: pio::Tx<(PIO, SM)>,
 }
+
+/// RS-485/multiprocessor address-match state for [`PioUartRx::set_address_match`].
+#[derive(Clone, Copy, Debug)]
+struct AddressFilter {
+    /// `addr & mask`, precomputed so matching is a single comparison.
+    addr: u16,
+    mask: u16,
+    /// Whether the most recently seen address frame matched.
+    matched: bool,
+}
+
+impl AddressFilter {
+    /// Whether an address frame's `word` matches this filter's `addr`/`mask`.
+    fn matches(&self, word: u16) -> bool {
+        word & self.mask == self.addr
+    }
+}
 /// Represents the Tx part of a UART interface using the RP2040's PIO hardware.
 ///
 /// # Type Parameters
@@ -115,11 +255,32 @@ pub struct PioUartRx<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex, State> {
 pub struct PioUartTx<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex, State> {
     tx: pio::Tx<(PIO, SM)>,
     sm: StateMachine<(PIO, SM), State>,
+    config: PioUartConfig,
     // The following fields are use to restore the original state in `free()`
     _tx_pin: Pin<PinID, PIO::PinFunction, PullNone>,
     _rx: pio::Rx<(PIO, SM)>,
 }
 
+/// Half-duplex RS-485 variant of [`PioUartTx`], obtained from
+/// [`PioUartTx::new_rs485`]. Asserts `de_pin` for the exact duration of
+/// each transmission using a second side-set bit in the PIO program,
+/// instead of a timed delay after the fact.
+///
+/// # Type Parameters
+/// - `TxID`: The PinId for the TX pin.
+/// - `DeID`: The PinId for the DE pin; must be the GPIO immediately after `TxID`.
+/// - `SM`:  The state machine to use.
+/// - `State`: The state of the UART interface, either `pio::Stopped` or `pio::Running`.
+pub struct PioUartTxRs485<TxID: PinId, DeID: PinId, PIO: PIOExt, SM: StateMachineIndex, State> {
+    tx: pio::Tx<(PIO, SM)>,
+    sm: StateMachine<(PIO, SM), State>,
+    config: PioUartConfig,
+    // The following fields are use to restore the original state in `free()`
+    _tx_pin: Pin<TxID, PIO::PinFunction, PullNone>,
+    _de_pin: Pin<DeID, PIO::PinFunction, PullNone>,
+    _rx: pio::Rx<(PIO, SM)>,
+}
+
 /// Token of the already installed UART Rx program. To be obtained with [`install_rx_program`].
 pub struct RxProgram<PIO: PIOExt> {
     program: InstalledProgram<PIO>,
@@ -128,6 +289,11 @@ pub struct RxProgram<PIO: PIOExt> {
 pub struct TxProgram<PIO: PIOExt> {
     program: InstalledProgram<PIO>,
 }
+/// Token of the already installed RS-485 UART Tx program. To be obtained
+/// with [`install_tx_rs485_program`].
+pub struct TxRs485Program<PIO: PIOExt> {
+    program: InstalledProgram<PIO>,
+}
 
 impl<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex> PioUartRx<PinID, PIO, SM, pio::Stopped> {
     /// Create a new [`PioUartRx`] instance.
@@ -137,23 +303,28 @@ impl<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex> PioUartRx<PinID, PIO, SM,
     /// - `rx_pin`: The RX pin configured with `FunctionPioX` and `PullUp`. Use [`pin.gpioX.reconfigure()`](https://docs.rs/rp2040-hal/latest/rp2040_hal/gpio/struct.Pin.html#method.reconfigure).
     /// - `sm`: A PIO state machine instance.
     /// - `rx_program`: The installed Rx program.
+    /// - `config`: The frame format (data bits, parity, stop bits) to receive.
     /// - `baud`: Desired baud rate.
     /// - `system_freq`: System frequency.
     pub fn new(
         rx_pin: Pin<PinID, PIO::PinFunction, PullUp>,
         rx_sm: UninitStateMachine<(PIO, SM)>,
         rx_program: &mut RxProgram<PIO>,
+        config: PioUartConfig,
         baud: fugit::HertzU32,
         system_freq: fugit::HertzU32,
     ) -> Self {
         let div = system_freq.to_Hz() as f32 / (8f32 * baud.to_Hz() as f32);
         let rx_id = rx_pin.id().num;
 
-        let (rx_sm, rx, tx) = Self::build_rx(rx_program, rx_id, rx_sm, div);
+        let (rx_sm, rx, tx) = Self::build_rx(rx_program, rx_id, rx_sm, config, div);
 
         Self {
             rx,
             sm: rx_sm,
+            config,
+            address_filter: None,
+            pending_error: None,
             _rx_pin: rx_pin,
             _tx: tx,
         }
@@ -162,6 +333,7 @@ impl<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex> PioUartRx<PinID, PIO, SM,
         token: &mut RxProgram<PIO>,
         rx_id: u8,
         sm: UninitStateMachine<(PIO, SM)>,
+        config: PioUartConfig,
         div: f32,
     ) -> (
         StateMachine<(PIO, SM), pio::Stopped>,
@@ -176,11 +348,22 @@ impl<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex> PioUartRx<PinID, PIO, SM,
             .jmp_pin(rx_id)
             .in_shift_direction(ShiftDirection::Right)
             .autopush(false)
-            .push_threshold(32)
+            .push_threshold(config.frame_bits() as u16)
             .buffers(pio::Buffers::OnlyRx)
             .build(sm);
         sm.set_pindirs([(rx_id, pio::PinDir::Input)].into_iter());
         sm.set_clock_divisor(div);
+        // Preload the `X` scratch register with `frame_bits - 1`: the
+        // program counts down through it once per frame, so a single
+        // assembled program serves every `data_bits`/`parity` combination.
+        sm.exec_instruction(Instruction {
+            operands: InstructionOperands::SET {
+                destination: SetDestination::X,
+                data: config.frame_bits() - 1,
+            },
+            delay: 0,
+            side_set: None,
+        });
         (sm, rx, tx)
     }
     /// Enables the UART, transitioning it to the `Running` state.
@@ -192,6 +375,9 @@ impl<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex> PioUartRx<PinID, PIO, SM,
         PioUartRx {
             sm: self.sm.start(),
             rx: self.rx,
+            config: self.config,
+            address_filter: self.address_filter,
+            pending_error: self.pending_error,
             _rx_pin: self._rx_pin,
             _tx: self._tx,
         }
@@ -213,29 +399,33 @@ impl<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex> PioUartRx<PinID, PIO, SM,
 
 impl<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex> PioUartTx<PinID, PIO, SM, pio::Stopped> {
     /// Create a new [`PioUartTx`] instance.
-    /// Requires the [`TxProgram`] to be already installed (see [`install_tx_program`]).
+    /// Requires the [`TxProgram`] to be already installed (see [`install_tx_program`]), matching
+    /// `config.stop_bits`.
     ///
     /// # Arguments
     /// - `tx_pin`: The TX pin configured with `FunctionPioX` and `PullNone`. Use [`pin.gpioX.reconfigure()`](https://docs.rs/rp2040-hal/latest/rp2040_hal/gpio/struct.Pin.html#method.reconfigure).
     /// - `sm`: A PIO state machine instance.
     /// - `tx_program`: The installed Tx program.
+    /// - `config`: The frame format (data bits, parity, stop bits) to transmit.
     /// - `baud`: Desired baud rate.
     /// - `system_freq`: System frequency.
     pub fn new(
         tx_pin: Pin<PinID, PIO::PinFunction, PullNone>,
         sm: UninitStateMachine<(PIO, SM)>,
         tx_program: &mut TxProgram<PIO>,
+        config: PioUartConfig,
         baud: fugit::HertzU32,
         system_freq: fugit::HertzU32,
     ) -> Self {
         let div = system_freq.to_Hz() as f32 / (8f32 * baud.to_Hz() as f32);
         let tx_id = tx_pin.id().num;
 
-        let (tx_sm, rx, tx) = Self::build_tx(tx_program, tx_id, sm, div);
+        let (tx_sm, rx, tx) = Self::build_tx(tx_program, tx_id, sm, config, div);
 
         Self {
             tx,
             sm: tx_sm,
+            config,
             _tx_pin: tx_pin,
             _rx: rx,
         }
@@ -244,6 +434,7 @@ impl<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex> PioUartTx<PinID, PIO, SM,
         token: &mut TxProgram<PIO>,
         tx_id: u8,
         sm: UninitStateMachine<(PIO, SM)>,
+        config: PioUartConfig,
         div: f32,
     ) -> (
         StateMachine<(PIO, SM), pio::Stopped>,
@@ -256,13 +447,25 @@ impl<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex> PioUartTx<PinID, PIO, SM,
         let (mut sm, rx, tx) = builder
             .out_shift_direction(ShiftDirection::Right)
             .autopull(false)
-            .pull_threshold(32)
+            .pull_threshold(config.frame_bits() as u16)
+            .set_pins(tx_id, 1)
             .buffers(pio::Buffers::OnlyTx)
             .out_pins(tx_id, 1)
             .side_set_pin_base(tx_id)
             .build(sm);
         sm.set_pindirs([(tx_id, pio::PinDir::Output)].into_iter());
         sm.set_clock_divisor(div);
+        // See the matching comment in `PioUartRx::build_rx`: `X` holds
+        // `frame_bits - 1` so the same two (1/2 stop bit) programs serve
+        // every `data_bits`/`parity` combination.
+        sm.exec_instruction(Instruction {
+            operands: InstructionOperands::SET {
+                destination: SetDestination::X,
+                data: config.frame_bits() - 1,
+            },
+            delay: 0,
+            side_set: None,
+        });
         (sm, rx, tx)
     }
     /// Enables the UART, transitioning it to the `Running` state.
@@ -274,6 +477,7 @@ impl<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex> PioUartTx<PinID, PIO, SM,
         PioUartTx {
             sm: self.sm.start(),
             tx: self.tx,
+            config: self.config,
             _tx_pin: self._tx_pin,
             _rx: self._rx,
         }
@@ -291,6 +495,137 @@ impl<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex> PioUartTx<PinID, PIO, SM,
         let (tx_sm, _) = self.sm.uninit(self._rx, self.tx);
         (tx_sm, self._tx_pin)
     }
+    /// Create a new half-duplex RS-485 [`PioUartTxRs485`] instance, which
+    /// asserts `de_pin` (a transceiver driver-enable/DE pin) for the exact
+    /// duration of each transmission using a second side-set bit in the
+    /// PIO program, rather than [`Self::flush`]'s guessed delay.
+    /// Requires the [`TxRs485Program`] to be already installed (see
+    /// [`install_tx_rs485_program`]).
+    ///
+    /// # Arguments
+    /// - `tx_pin`: The TX pin configured with `FunctionPioX` and `PullNone`.
+    /// - `de_pin`: The DE pin, also configured with `FunctionPioX` and `PullNone`. Must be the GPIO immediately after `tx_pin` (PIO side-set pins are a contiguous block).
+    /// - `sm`: A PIO state machine instance.
+    /// - `tx_program`: The installed RS-485 Tx program.
+    /// - `config`: The frame format (data bits, parity, stop bits) to transmit.
+    /// - `baud`: Desired baud rate.
+    /// - `system_freq`: System frequency.
+    ///
+    /// `uart_tx_rs485` (see [`install_tx_rs485_program`]) only assembles a
+    /// single stop bit -- unlike the non-RS-485 path, which has a
+    /// [`StopBits::Two`] variant (`uart_tx_2sb`, see [`install_tx_program`]),
+    /// so `config.stop_bits` must be [`StopBits::One`].
+    ///
+    /// # Panics
+    /// Panics if `de_pin` is not the GPIO immediately after `tx_pin`, or if
+    /// `config.stop_bits` isn't [`StopBits::One`].
+    pub fn new_rs485<DeID: PinId>(
+        tx_pin: Pin<PinID, PIO::PinFunction, PullNone>,
+        de_pin: Pin<DeID, PIO::PinFunction, PullNone>,
+        sm: UninitStateMachine<(PIO, SM)>,
+        tx_program: &mut TxRs485Program<PIO>,
+        config: PioUartConfig,
+        baud: fugit::HertzU32,
+        system_freq: fugit::HertzU32,
+    ) -> PioUartTxRs485<PinID, DeID, PIO, SM, pio::Stopped> {
+        let tx_id = tx_pin.id().num;
+        let de_id = de_pin.id().num;
+        assert_eq!(
+            de_id,
+            tx_id + 1,
+            "RS-485 DE pin must be the GPIO immediately after the TX pin"
+        );
+        assert_eq!(
+            config.stop_bits,
+            StopBits::One,
+            "uart_tx_rs485 only assembles a single stop bit"
+        );
+
+        let div = system_freq.to_Hz() as f32 / (8f32 * baud.to_Hz() as f32);
+        let (tx_sm, rx, tx) = PioUartTxRs485::<PinID, DeID, PIO, SM, pio::Stopped>::build_tx_rs485(
+            tx_program, tx_id, de_id, sm, config, div,
+        );
+
+        PioUartTxRs485 {
+            tx,
+            sm: tx_sm,
+            config,
+            _tx_pin: tx_pin,
+            _de_pin: de_pin,
+            _rx: rx,
+        }
+    }
+}
+
+impl<TxID: PinId, DeID: PinId, PIO: PIOExt, SM: StateMachineIndex>
+    PioUartTxRs485<TxID, DeID, PIO, SM, pio::Stopped>
+{
+    fn build_tx_rs485(
+        token: &mut TxRs485Program<PIO>,
+        tx_id: u8,
+        de_id: u8,
+        sm: UninitStateMachine<(PIO, SM)>,
+        config: PioUartConfig,
+        div: f32,
+    ) -> (
+        StateMachine<(PIO, SM), pio::Stopped>,
+        pio::Rx<(PIO, SM)>,
+        pio::Tx<(PIO, SM)>,
+    ) {
+        // SAFETY: Program can not be uninstalled, because it can not be accessed
+        let program = unsafe { token.program.share() };
+        let builder = PIOBuilder::from_installed_program(program);
+        let (mut sm, rx, tx) = builder
+            .out_shift_direction(ShiftDirection::Right)
+            .autopull(false)
+            .pull_threshold(config.frame_bits() as u16)
+            .set_pins(tx_id, 1)
+            .buffers(pio::Buffers::OnlyTx)
+            .out_pins(tx_id, 1)
+            .side_set_pin_base(tx_id)
+            .build(sm);
+        sm.set_pindirs([(tx_id, pio::PinDir::Output), (de_id, pio::PinDir::Output)].into_iter());
+        sm.set_clock_divisor(div);
+        // See the matching comment in `PioUartTx::build_tx`.
+        sm.exec_instruction(Instruction {
+            operands: InstructionOperands::SET {
+                destination: SetDestination::X,
+                data: config.frame_bits() - 1,
+            },
+            delay: 0,
+            side_set: None,
+        });
+        (sm, rx, tx)
+    }
+    /// Enables the UART, transitioning it to the `Running` state.
+    ///
+    /// # Returns
+    /// An instance of `PioUartTxRs485` in the `Running` state.
+    #[inline]
+    pub fn enable(self) -> PioUartTxRs485<TxID, DeID, PIO, SM, pio::Running> {
+        PioUartTxRs485 {
+            sm: self.sm.start(),
+            tx: self.tx,
+            config: self.config,
+            _tx_pin: self._tx_pin,
+            _de_pin: self._de_pin,
+            _rx: self._rx,
+        }
+    }
+    /// Frees the underlying resources, returning the SM instance and the pins.
+    ///
+    /// # Returns
+    /// A tuple containing the used SM, the TX pin, and the DE pin.
+    pub fn free(
+        self,
+    ) -> (
+        UninitStateMachine<(PIO, SM)>,
+        Pin<TxID, PIO::PinFunction, PullNone>,
+        Pin<DeID, PIO::PinFunction, PullNone>,
+    ) {
+        let (tx_sm, _) = self.sm.uninit(self._rx, self.tx);
+        (tx_sm, self._tx_pin, self._de_pin)
+    }
 }
 
 impl<RXID: PinId, TXID: PinId, PIO: PIOExt> PioUart<RXID, TXID, PIO, pio::Stopped> {
@@ -303,6 +638,7 @@ impl<RXID: PinId, TXID: PinId, PIO: PIOExt> PioUart<RXID, TXID, PIO, pio::Stoppe
     /// - `rx_pin`: The RX pin configured with `FunctionPioX` and `PullUp`. Use [`pin.gpioX.reconfigure()`](https://docs.rs/rp2040-hal/latest/rp2040_hal/gpio/struct.Pin.html#method.reconfigure).
     /// - `tx_pin`: The TX pin configured with `FunctionPioX` and `PullNone`. Use [`pin.gpioX.reconfigure()`](https://docs.rs/rp2040-hal/latest/rp2040_hal/gpio/struct.Pin.html#method.reconfigure).
     /// - `resets`: A mutable reference to the RP2040 resets.
+    /// - `config`: The frame format (data bits, parity, stop bits) used by both RX and TX.
     /// - `baud`: Desired baud rate.
     /// - `system_freq`: System frequency.
     pub fn new(
@@ -310,14 +646,15 @@ impl<RXID: PinId, TXID: PinId, PIO: PIOExt> PioUart<RXID, TXID, PIO, pio::Stoppe
         rx_pin: Pin<RXID, <PIO as PIOExt>::PinFunction, PullUp>,
         tx_pin: Pin<TXID, <PIO as PIOExt>::PinFunction, PullNone>,
         resets: &mut rp2040_hal::pac::RESETS,
+        config: PioUartConfig,
         baud: fugit::HertzU32,
         system_freq: fugit::HertzU32,
     ) -> Self {
         let (mut pio, sm0, sm1, sm2, sm3) = pio.split(resets);
         let mut rx_program = install_rx_program(&mut pio).ok().unwrap(); // Should never fail, because no program was loaded yet
-        let mut tx_program = install_tx_program(&mut pio).ok().unwrap(); // Should never fail, because no program was loaded yet
-        let rx = PioUartRx::new(rx_pin, sm0, &mut rx_program, baud, system_freq);
-        let tx = PioUartTx::new(tx_pin, sm1, &mut tx_program, baud, system_freq);
+        let mut tx_program = install_tx_program(&mut pio, config.stop_bits).ok().unwrap(); // Should never fail, because no program was loaded yet
+        let rx = PioUartRx::new(rx_pin, sm0, &mut rx_program, config, baud, system_freq);
+        let tx = PioUartTx::new(tx_pin, sm1, &mut tx_program, config, baud, system_freq);
         Self {
             rx,
             tx,
@@ -366,37 +703,260 @@ impl<RXID: PinId, TXID: PinId, PIO: PIOExt> PioUart<RXID, TXID, PIO, pio::Stoppe
     }
 }
 
+// RP2040 datasheet 3.4.2.5/3.7: a PIO block's sticky IRQ flags (set from a
+// program with `irq <n> rel`) live in its `IRQ` register, at a fixed
+// offset from the same PIO0/PIO1 base `asynch` derives `IRQ0_INTE`/`INTS`
+// from. A flag is cleared by writing a 1 back to its bit.
+const PIO_IRQ_OFFSET: u32 = 0x030;
+
+fn pio_irq_ptr<PIO: PIOExt>() -> *mut u32 {
+    let base = if PIO::id() == 0 {
+        0x5020_0000
+    } else {
+        0x5030_0000
+    };
+    (base + PIO_IRQ_OFFSET) as *mut u32
+}
+
+/// Checks and clears `uart_rx`'s line-error flag (raised by `irq 0 rel`
+/// when a frame's stop bit reads low) for one state machine.
+fn take_line_error<PIO: PIOExt>(sm: u8) -> bool {
+    let ptr = pio_irq_ptr::<PIO>();
+    critical_section::with(|_| unsafe {
+        let bit = 1 << sm;
+        if core::ptr::read_volatile(ptr) & bit != 0 {
+            core::ptr::write_volatile(ptr, bit); // write-1-to-clear
+            true
+        } else {
+            false
+        }
+    })
+}
+
+/// The FIFO operations [`drain_words`] needs from a receiving state
+/// machine. The real implementation is just `pio::Rx` plus [`take_line_error`];
+/// this is split out purely so [`drain_words`]'s draining and
+/// error-deferral logic can be unit-tested against a fake FIFO below,
+/// without real PIO hardware.
+trait RxFifo {
+    /// Pops one raw, unshifted 32-bit FIFO word, if one is queued.
+    fn read(&mut self) -> Option<u32>;
+    /// Whether the FIFO is still full after the most recent `read`.
+    fn is_full(&self) -> bool;
+    /// Checks and clears the line-error flag for the frame `read` just returned.
+    fn take_line_error(&mut self) -> bool;
+}
+
+impl<PIO: PIOExt, SM: StateMachineIndex> RxFifo for pio::Rx<(PIO, SM)> {
+    fn read(&mut self) -> Option<u32> {
+        pio::Rx::read(self)
+    }
+    fn is_full(&self) -> bool {
+        pio::Rx::is_full(self)
+    }
+    fn take_line_error(&mut self) -> bool {
+        take_line_error::<PIO>(SM::id())
+    }
+}
+
+/// Checks the parity bit of a received `frame` (`data_bits` data bits, then
+/// the parity bit immediately above them) against `parity`.
+fn parity_ok(frame: u16, data_bits: u8, parity: Parity) -> bool {
+    if parity == Parity::None {
+        return true;
+    }
+    let data = frame & ((1 << data_bits) - 1);
+    let received = (frame >> data_bits) & 0x01;
+    received == parity_bit(data, parity)
+}
+
+/// Drains up to `buf.len()` 9-bit words from `fifo` into `buf`, the shared
+/// core of [`PioUartRx::read_words`]: applies `address_filter` the same way,
+/// and on a line error/parity mismatch/overrun, stops and returns the words
+/// already decoded alongside the error to defer, rather than discarding them.
+fn drain_words<F: RxFifo>(
+    fifo: &mut F,
+    frame_bits: u8,
+    data_bits: u8,
+    parity: Parity,
+    address_filter: &mut Option<AddressFilter>,
+    buf: &mut [u16],
+) -> (usize, Option<PioSerialError>) {
+    let shift = 32 - frame_bits as u32;
+
+    let mut n = 0;
+    let mut pending_error = None;
+    while n < buf.len() {
+        let raw = match fifo.read() {
+            Some(raw) => raw,
+            None => break,
+        };
+        let frame = (raw >> shift) as u16;
+        if fifo.take_line_error() {
+            pending_error = Some(if frame == 0 {
+                PioSerialError::Break
+            } else {
+                PioSerialError::Framing
+            });
+            break;
+        }
+        if parity != Parity::None && !parity_ok(frame, data_bits, parity) {
+            pending_error = Some(PioSerialError::Parity);
+            break;
+        }
+        // Masking to `data_bits` (not a flat 9 bits) strips the parity bit
+        // at bit 8 when `parity != Parity::None` shrinks the data portion
+        // below 9 bits -- e.g. 8 data bits + Even parity puts the parity
+        // bit at bit 8, not a marker bit. For 9N1 (`data_bits == 9`,
+        // `parity == None`, the only framing address-match requires), this
+        // is the same 9-bit mask as before: the marker bit is the 9th data
+        // bit itself.
+        let word = frame & ((1u16 << data_bits) - 1);
+
+        // In address-match mode, address frames (marker bit set) are never
+        // delivered to `buf` -- they only update which node is currently
+        // addressed -- and data frames are discarded while no address has
+        // matched.
+        if let Some(filter) = address_filter {
+            if word & 0x100 != 0 {
+                filter.matched = filter.matches(word);
+                continue;
+            }
+            if !filter.matched {
+                continue;
+            }
+        }
+
+        buf[n] = word;
+        n += 1;
+    }
+    // The FIFO is still full after draining everything `buf` had room for:
+    // `uart_rx`'s blocking `push` may have stalled the state machine, so a
+    // further frame could already be slipping by on the wire. Flag it
+    // instead of failing outright, so the caller gets to keep the `n` words
+    // just drained; the error surfaces on the next call. A line/parity
+    // error above already stopped the loop and takes priority, so don't
+    // clobber it with an overrun.
+    if pending_error.is_none() && fifo.is_full() {
+        pending_error = Some(PioSerialError::Overrun);
+    }
+    (n, pending_error)
+}
+
 impl<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex> PioUartRx<PinID, PIO, SM, pio::Running> {
-    /// Reads raw data into a buffer.
+    /// Reads up to `buf.len()` 9-bit words into `buf`, each masked to the
+    /// low `data_bits` bits, so a parity bit is never returned (for
+    /// 9-bit-no-parity framing, bit 8 is the marker/address bit).
+    ///
+    /// # Returns
+    /// `Ok(usize)`: Number of words read; fewer than `buf.len()` if the FIFO ran dry.
+    /// `Err(PioSerialError)`: If an error occurs, e.g. a parity mismatch, a
+    /// framing/break condition, or the FIFO overrunning. Any error is
+    /// reported only once the words already drained into `buf` before it
+    /// was detected have been returned -- it never discards data that's
+    /// already safely buffered; the error surfaces on the next call instead.
+    pub fn read_words(&mut self, buf: &mut [u16]) -> Result<usize, PioSerialError> {
+        if let Some(err) = self.pending_error.take() {
+            return Err(err);
+        }
+        let (n, pending_error) = drain_words(
+            &mut self.rx,
+            self.config.frame_bits(),
+            self.config.data_bits,
+            self.config.parity,
+            &mut self.address_filter,
+            buf,
+        );
+        self.pending_error = pending_error;
+        Ok(n)
+    }
+    /// Enables RS-485/multiprocessor address-match mode.
+    ///
+    /// While no address frame has matched, incoming data frames (marker bit
+    /// clear) are discarded by [`Self::read_words`]/[`Self::read_raw`]. Once
+    /// an address frame (marker bit set) whose bits under `mask` equal
+    /// `addr & mask` arrives, subsequent data frames are delivered until a non-matching
+    /// address frame arrives. Address frames themselves are never delivered
+    /// to the data buffer.
+    ///
+    /// Requires 9N1 framing (`data_bits == 9`, `parity == Parity::None`):
+    /// the marker bit is hardcoded to bit 8 of the 9-bit word, which is only
+    /// the 9th data bit, and not a parity bit, under that framing.
+    ///
+    /// # Panics
+    /// Panics if `self`'s config isn't 9 data bits with no parity.
+    pub fn set_address_match(&mut self, addr: u16, mask: u16) {
+        assert!(
+            self.config.data_bits == 9 && self.config.parity == Parity::None,
+            "address-match mode requires 9N1 framing (9 data bits, no parity)"
+        );
+        self.address_filter = Some(AddressFilter {
+            addr: addr & mask,
+            mask,
+            matched: false,
+        });
+    }
+    /// Disables address-match mode: all data frames are delivered again.
+    pub fn clear_address_match(&mut self) {
+        self.address_filter = None;
+    }
+    /// Reads raw data into a buffer, using the 2-bytes-per-word convention
+    /// expected by the `embedded_io` byte traits. Prefer [`Self::read_words`]
+    /// for new code.
     ///
     /// # Arguments
     /// - `buf`: A mutable slice of u8 to store the read data.
     ///
     /// # Returns
     /// `Ok(usize)`: Number of bytes read.
-    /// `Err(())`: If an error occurs.
-    pub fn read_raw(&mut self, mut buf: &mut [u8]) -> Result<usize, ()> {
-        let buf_len = buf.len();
-        //Because of the 9 bit modification, bytes will always arrive in multiples of 2
-        //Eg 1x 9-bit type received will be read here as 2x bytes.  The first will contain the extra bit in the 0x01 position,
-    	//while the second will contain bits 7-0.
-    	if buf_len < 2 {
-    	    return Err(());
-    	}
-
-        'outer: loop {
-            while let Some(b) = self.rx.read() {
-                buf[0] = (b >> 24) as u8;
-                buf = &mut buf[1..];
-                if buf.len() == 0 {
-                    break 'outer;
-                }
-            }
-            if (buf_len - buf.len()) %2 == 0 {
-                break 'outer;
+    /// `Err(PioSerialError)`: If an error occurs, e.g. a parity mismatch.
+    pub fn read_raw(&mut self, buf: &mut [u8]) -> Result<usize, PioSerialError> {
+        //Because of the 9 bit modification, bytes arrive in multiples of 2:
+        //1x 9-bit word is read here as 2x bytes. The first contains the
+        //marker/address bit in the 0x01 position, the second bits 7-0.
+        if buf.len() < 2 {
+            return Err(PioSerialError::IO);
+        }
+
+        let mut word = [0u16; 1];
+        let mut n = 0;
+        while n + 2 <= buf.len() {
+            if self.read_words(&mut word)? == 0 {
+                break;
             }
+            buf[n] = (word[0] >> 8) as u8 & 0x01;
+            buf[n + 1] = word[0] as u8;
+            n += 2;
+        }
+        Ok(n)
+    }
+    /// Starts a DMA transfer that reads incoming frames from the RX FIFO
+    /// into `buf` without blocking the core. `buf` receives the raw 32-bit
+    /// FIFO words, unshifted and unmasked: the ISR shifts right, each new
+    /// bit entering at bit 31, so a frame's `frame_bits` bits (data, then
+    /// any parity bit) sit in the top bits of the word, not the low ones. Shifting a word
+    /// right by `32 - frame_bits` gives the frame that [`Self::read_words`]
+    /// decodes; the DMA transfer does not do this.
+    ///
+    /// Consumes `self` so a second transfer can't be started on the same
+    /// FIFO until this one's [`PioUartRxDmaTransfer::wait`] hands it back.
+    /// Address matching (see [`Self::set_address_match`]) is not applied to
+    /// DMA transfers, and neither is parity checking -- see
+    /// [`PioUartRxDmaTransfer::wait`] for the line-error caveats.
+    pub fn read_dma<CH: SingleChannel, B: WriteTarget<TransmittedWord = u32>>(
+        self,
+        ch: CH,
+        buf: B,
+    ) -> PioUartRxDmaTransfer<PinID, PIO, SM, CH, B> {
+        PioUartRxDmaTransfer {
+            transfer: single_buffer::Transfer::start(ch, self.rx, buf),
+            sm: self.sm,
+            config: self.config,
+            address_filter: self.address_filter,
+            pending_error: self.pending_error,
+            _rx_pin: self._rx_pin,
+            _tx: self._tx,
         }
-    	Ok(buf_len - buf.len())
     }
     /// Stops the UART, transitioning it back to the `Stopped` state.
     ///
@@ -407,13 +967,132 @@ impl<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex> PioUartRx<PinID, PIO, SM,
         PioUartRx {
             sm: self.sm.stop(),
             rx: self.rx,
+            config: self.config,
+            address_filter: self.address_filter,
+            pending_error: self.pending_error,
             _rx_pin: self._rx_pin,
             _tx: self._tx,
         }
     }
+    /// Arms the PIO RX-FIFO-not-empty interrupt on `PIOx_IRQ_0` for this
+    /// state machine, so [`asynch::on_pio_irq0`] wakes the task parked on
+    /// [`asynch::PioUartRxAsync::read`] once a frame arrives.
+    pub(crate) fn arm_rx_not_empty_interrupt(&self) {
+        asynch::enable_rx_not_empty_interrupt::<PIO>(SM::id());
+    }
+    /// Converts this synchronous RX endpoint into a [`asynch::PioUartRxAsync`],
+    /// backed by the RX-not-empty interrupt instead of polling the FIFO from
+    /// a busy loop.
+    ///
+    /// `_token` is proof that `PIOx_IRQ_0` is wired to [`asynch::on_pio_irq0`].
+    pub fn into_async(
+        self,
+        _token: &asynch::PioIrq0Token<PIO>,
+    ) -> asynch::PioUartRxAsync<PinID, PIO, SM> {
+        asynch::PioUartRxAsync { inner: self }
+    }
+}
+
+/// A DMA transfer reading frames from a [`PioUartRx`]'s FIFO.
+///
+/// Obtained from [`PioUartRx::read_dma`]; call [`Self::wait`] to block until
+/// the transfer completes and get the DMA channel, buffer, and `PioUartRx`
+/// back -- see its doc comment for the line-error caveats that come with
+/// bypassing [`PioUartRx::read_words`].
+pub struct PioUartRxDmaTransfer<
+    PinID: PinId,
+    PIO: PIOExt,
+    SM: StateMachineIndex,
+    CH: SingleChannel,
+    B: WriteTarget<TransmittedWord = u32>,
+> {
+    transfer: single_buffer::Transfer<CH, pio::Rx<(PIO, SM)>, B>,
+    sm: StateMachine<(PIO, SM), pio::Running>,
+    config: PioUartConfig,
+    address_filter: Option<AddressFilter>,
+    pending_error: Option<PioSerialError>,
+    _rx_pin: Pin<PinID, PIO::PinFunction, PullUp>,
+    _tx: pio::Tx<(PIO, SM)>,
 }
+
+impl<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex, CH: SingleChannel, B>
+    PioUartRxDmaTransfer<PinID, PIO, SM, CH, B>
+where
+    B: WriteTarget<TransmittedWord = u32>,
+{
+    /// Returns `true` once the DMA transfer has finished.
+    pub fn is_done(&self) -> bool {
+        self.transfer.is_done()
+    }
+    /// Blocks until the DMA transfer completes.
+    ///
+    /// Unlike [`PioUartRx::read_words`], a framing/break condition (see
+    /// `uart_rx`'s `irq 0 rel`) can't be attributed to a particular frame in
+    /// `buf`: the flag is sticky for the whole transfer, not per-word, so
+    /// this only reports whether *some* frame in the transfer had a line
+    /// error, not which one. Parity is not checked at all -- `read_words`
+    /// applies it per frame as it's drained, but a DMA transfer never goes
+    /// through that code path.
+    ///
+    /// # Returns
+    /// The DMA channel, the buffer, the now-idle [`PioUartRx`], and whether
+    /// a framing/break condition was seen (and is now cleared) somewhere in
+    /// the transfer.
+    pub fn wait(
+        self,
+    ) -> (
+        CH,
+        B,
+        PioUartRx<PinID, PIO, SM, pio::Running>,
+        Result<(), PioSerialError>,
+    ) {
+        let (ch, rx, buf) = self.transfer.wait();
+        // `frame == 0` can't be distinguished from `Break` here the way
+        // `read_words` does, since no single frame is available to inspect
+        // -- report it as a generic `Framing` error instead.
+        let line_error = if take_line_error::<PIO>(SM::id()) {
+            Err(PioSerialError::Framing)
+        } else {
+            Ok(())
+        };
+        (
+            ch,
+            buf,
+            PioUartRx {
+                rx,
+                sm: self.sm,
+                config: self.config,
+                address_filter: self.address_filter,
+                pending_error: self.pending_error,
+                _rx_pin: self._rx_pin,
+                _tx: self._tx,
+            },
+            line_error,
+        )
+    }
+}
+
 impl<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex> PioUartTx<PinID, PIO, SM, pio::Running> {
-    /// Writes raw data from a buffer.
+    /// Writes 9-bit words from `words`, each masked to the low `data_bits`
+    /// bits, with the parity bit (if any) appended above them (for
+    /// 9-bit-no-parity framing, bit 8 is the marker/address bit). Blocks
+    /// while the TX FIFO is full.
+    ///
+    /// # Returns
+    /// `Ok(usize)`: Number of words written (always `words.len()`).
+    /// `Err(())`: If an error occurs.
+    pub fn write_words(&mut self, words: &[u16]) -> Result<usize, ()> {
+        for &word in words {
+            while self.tx.is_full() {
+                core::hint::spin_loop()
+            }
+            self.tx.write(self.encode_word(word));
+        }
+        Ok(words.len())
+    }
+    /// Writes raw data from a buffer, using the 2-bytes-per-word convention
+    /// expected by the `embedded_io` byte traits. Prefer [`Self::write_words`]
+    /// for new code.
     ///
     /// # Arguments
     /// - `buf`: A slice of u8 containing the data to write.
@@ -422,23 +1101,105 @@ impl<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex> PioUartTx<PinID, PIO, SM,
     /// `Ok(())`: On success.
     /// `Err(())`: If an error occurs.
     pub fn write_raw(&mut self, buf: &[u8]) -> Result<(), ()> {
-    // To provide 9 bit support, we expect to receive writes in multiples of 2      
-       for n in 0..buf.len()/2 {
-          while self.tx.is_full() {
-            core::hint::spin_loop()
-          }
-    	  self.tx.write(u16::from_le_bytes([ buf[(n*2) +1], buf[n*2]&0x01 ]  ) as u32);
+        // To provide 9 bit support, we expect to receive writes in multiples of 2
+        for n in 0..buf.len() / 2 {
+            let word = u16::from_le_bytes([buf[(n * 2) + 1], buf[n * 2] & 0x01]);
+            self.write_words(&[word])?;
         }
         Ok(())
     }
+    fn encode_word(&self, word: u16) -> u32 {
+        let data = word & ((1 << self.config.data_bits) - 1);
+        (data | (parity_bit(data, self.config.parity) << self.config.data_bits)) as u32
+    }
+    /// Writes a single 9-bit word to the TX FIFO if there's currently room,
+    /// without blocking. Returns `false` (and writes nothing) if the FIFO is
+    /// full -- used by [`asynch::PioUartTxAsync::write`] so an async write
+    /// never busy-spins the core the way [`Self::write_words`] does.
+    pub(crate) fn try_write_word(&mut self, word: u16) -> bool {
+        if self.tx.is_full() {
+            return false;
+        }
+        self.tx.write(self.encode_word(word));
+        true
+    }
+    /// Drives the line low for `bit_times` bit periods, signalling a break
+    /// condition to the far end (see [`PioSerialError::Break`]), then
+    /// returns it to idle-high. Waits for any already-queued frame to
+    /// finish transmitting first.
+    ///
+    /// A conventional break is at least one full frame long, i.e.
+    /// `config.frame_bits() + 2` (start bit plus stop bit(s)) or more.
+    ///
+    /// # Arguments
+    /// - `bit_times`: How many bit periods to hold the line low for.
+    pub fn send_break(&mut self, bit_times: u32) {
+        self.flush();
+        // Force the line low directly with `set`, the same way `build_tx`
+        // preloads `X` -- bypassing the program's usual `pull`/`out` path.
+        // The PIO clock runs at 8 cycles/bit (see `build_tx`), and the
+        // program's `.side_set 1 opt` leaves only 3 of the 5 delay/side-set
+        // bits for `delay`, so long breaks are split into repeated
+        // up-to-8-cycle instructions.
+        let mut remaining_cycles = bit_times.saturating_mul(8);
+        while remaining_cycles > 0 {
+            let delay = remaining_cycles.min(8) - 1;
+            self.sm.exec_instruction(Instruction {
+                operands: InstructionOperands::SET {
+                    destination: SetDestination::PINS,
+                    data: 0,
+                },
+                delay: delay as u8,
+                side_set: None,
+            });
+            remaining_cycles -= delay + 1;
+        }
+        // Restore idle-high before handing control back to the program.
+        self.sm.exec_instruction(Instruction {
+            operands: InstructionOperands::SET {
+                destination: SetDestination::PINS,
+                data: 1,
+            },
+            delay: 0,
+            side_set: None,
+        });
+    }
     /// Flushes the UART transmit buffer.
-    fn flush(&mut self) {
+    pub(crate) fn flush(&mut self) {
         while !self.tx.is_empty() {
             core::hint::spin_loop()
         }
         //FIXME This was found by trial and error
         cortex_m::asm::delay(500 * 125);
     }
+    /// Arms the PIO TX-FIFO-not-full interrupt on `PIOx_IRQ_0` for this
+    /// state machine, so [`asynch::on_pio_irq0`] wakes the task parked on
+    /// [`asynch::PioUartTxAsync::write`] once there's room in the FIFO.
+    pub(crate) fn arm_tx_not_full_interrupt(&self) {
+        asynch::enable_tx_not_full_interrupt::<PIO>(SM::id());
+    }
+    /// Starts a DMA transfer that writes `buf` out through the TX FIFO
+    /// without blocking the core. `buf` must carry 32-bit FIFO words --
+    /// the same packed layout [`Self::write_words`] writes, only 16 bits
+    /// wider, since the PIO FIFO is 32 bits wide even though only the low
+    /// `frame_bits` bits (data bits plus any parity bit) of each word are
+    /// shifted out.
+    ///
+    /// Consumes `self` so a second transfer can't be started on the same
+    /// FIFO until this one's [`PioUartTxDmaTransfer::wait`] hands it back.
+    pub fn write_dma<CH: SingleChannel, B: ReadTarget<ReceivedWord = u32>>(
+        self,
+        ch: CH,
+        buf: B,
+    ) -> PioUartTxDmaTransfer<PinID, PIO, SM, CH, B> {
+        PioUartTxDmaTransfer {
+            transfer: single_buffer::Transfer::start(ch, buf, self.tx),
+            sm: self.sm,
+            config: self.config,
+            _tx_pin: self._tx_pin,
+            _rx: self._rx,
+        }
+    }
     /// Stops the UART, transitioning it back to the `Stopped` state.
     ///
     /// # Returns
@@ -448,23 +1209,168 @@ impl<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex> PioUartTx<PinID, PIO, SM,
         PioUartTx {
             sm: self.sm.stop(),
             tx: self.tx,
+            config: self.config,
+            _tx_pin: self._tx_pin,
+            _rx: self._rx,
+        }
+    }
+    /// Converts this synchronous TX endpoint into a [`asynch::PioUartTxAsync`],
+    /// backed by the TX-not-full interrupt instead of polling the FIFO from
+    /// a busy loop.
+    ///
+    /// `_token` is proof that `PIOx_IRQ_0` is wired to [`asynch::on_pio_irq0`].
+    pub fn into_async(
+        self,
+        _token: &asynch::PioIrq0Token<PIO>,
+    ) -> asynch::PioUartTxAsync<PinID, PIO, SM> {
+        asynch::PioUartTxAsync { inner: self }
+    }
+}
+
+impl<TxID: PinId, DeID: PinId, PIO: PIOExt, SM: StateMachineIndex>
+    PioUartTxRs485<TxID, DeID, PIO, SM, pio::Running>
+{
+    /// Writes 9-bit words from `words`, each masked to the low `data_bits`
+    /// bits, with the parity bit (if any) appended above them (for
+    /// 9-bit-no-parity framing, bit 8 is the marker/address bit). Blocks
+    /// while the TX FIFO is full. DE is asserted by the PIO program itself
+    /// for the duration of the frame.
+    ///
+    /// # Returns
+    /// `Ok(usize)`: Number of words written (always `words.len()`).
+    /// `Err(())`: If an error occurs.
+    pub fn write_words(&mut self, words: &[u16]) -> Result<usize, ()> {
+        for &word in words {
+            let data = word & ((1 << self.config.data_bits) - 1);
+            let frame = data | (parity_bit(data, self.config.parity) << self.config.data_bits);
+            while self.tx.is_full() {
+                core::hint::spin_loop()
+            }
+            self.tx.write(frame as u32);
+        }
+        Ok(words.len())
+    }
+    /// Writes raw data from a buffer, using the 2-bytes-per-word convention
+    /// expected by the `embedded_io` byte traits. Prefer [`Self::write_words`]
+    /// for new code.
+    ///
+    /// # Arguments
+    /// - `buf`: A slice of u8 containing the data to write.
+    ///
+    /// # Returns
+    /// `Ok(())`: On success.
+    /// `Err(())`: If an error occurs.
+    pub fn write_raw(&mut self, buf: &[u8]) -> Result<(), ()> {
+        // To provide 9 bit support, we expect to receive writes in multiples of 2
+        for n in 0..buf.len() / 2 {
+            let word = u16::from_le_bytes([buf[(n * 2) + 1], buf[n * 2] & 0x01]);
+            self.write_words(&[word])?;
+        }
+        Ok(())
+    }
+    /// Flushes the UART transmit buffer. Unlike [`PioUartTx::flush`], no
+    /// extra guessed delay is needed afterwards: the RS-485 PIO program
+    /// already holds DE asserted through the final stop bit and releases
+    /// it immediately after, so draining the FIFO is enough.
+    fn flush(&mut self) {
+        while !self.tx.is_empty() {
+            core::hint::spin_loop()
+        }
+    }
+    /// Stops the UART, transitioning it back to the `Stopped` state.
+    ///
+    /// # Returns
+    /// An instance of `PioUartTxRs485` in the `Stopped` state.
+    #[inline]
+    pub fn stop(self) -> PioUartTxRs485<TxID, DeID, PIO, SM, pio::Stopped> {
+        PioUartTxRs485 {
+            sm: self.sm.stop(),
+            tx: self.tx,
+            config: self.config,
             _tx_pin: self._tx_pin,
+            _de_pin: self._de_pin,
             _rx: self._rx,
         }
     }
 }
 
+/// A DMA transfer writing frames out through a [`PioUartTx`]'s FIFO.
+///
+/// Obtained from [`PioUartTx::write_dma`]; call [`Self::wait`] to block
+/// until the transfer completes and get the DMA channel, buffer, and
+/// `PioUartTx` back.
+pub struct PioUartTxDmaTransfer<
+    PinID: PinId,
+    PIO: PIOExt,
+    SM: StateMachineIndex,
+    CH: SingleChannel,
+    B: ReadTarget<ReceivedWord = u32>,
+> {
+    transfer: single_buffer::Transfer<CH, B, pio::Tx<(PIO, SM)>>,
+    sm: StateMachine<(PIO, SM), pio::Running>,
+    config: PioUartConfig,
+    _tx_pin: Pin<PinID, PIO::PinFunction, PullNone>,
+    _rx: pio::Rx<(PIO, SM)>,
+}
+
+impl<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex, CH: SingleChannel, B>
+    PioUartTxDmaTransfer<PinID, PIO, SM, CH, B>
+where
+    B: ReadTarget<ReceivedWord = u32>,
+{
+    /// Returns `true` once the DMA transfer has finished.
+    pub fn is_done(&self) -> bool {
+        self.transfer.is_done()
+    }
+    /// Blocks until the DMA transfer completes.
+    ///
+    /// # Returns
+    /// The DMA channel, the buffer, and the now-idle [`PioUartTx`].
+    pub fn wait(self) -> (CH, B, PioUartTx<PinID, PIO, SM, pio::Running>) {
+        let (ch, buf, tx) = self.transfer.wait();
+        (
+            ch,
+            buf,
+            PioUartTx {
+                tx,
+                sm: self.sm,
+                config: self.config,
+                _tx_pin: self._tx_pin,
+                _rx: self._rx,
+            },
+        )
+    }
+}
+
 /// Represents errors that can occur in the PIO UART.
-#[derive(core::fmt::Debug, defmt::Format)]
+#[derive(core::fmt::Debug, defmt::Format, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum PioSerialError {
     /// General IO error
     IO,
+    /// The received frame's parity bit did not match the configured parity.
+    Parity,
+    /// The stop bit wasn't high when sampled: the frame's bit timing (or
+    /// the sender's baud rate) has slipped.
+    Framing,
+    /// A new frame arrived in the RX FIFO before [`PioUartRx::read_words`]
+    /// drained the previous backlog.
+    Overrun,
+    /// RX was held low through an entire frame, including where the stop
+    /// bit should have been: a break condition, as sent by
+    /// [`PioUartTx::send_break`].
+    Break,
 }
 
 impl embedded_io::Error for PioSerialError {
     fn kind(&self) -> embedded_io::ErrorKind {
-        embedded_io::ErrorKind::Other
+        match self {
+            PioSerialError::IO => embedded_io::ErrorKind::Other,
+            PioSerialError::Parity => embedded_io::ErrorKind::InvalidData,
+            PioSerialError::Framing => embedded_io::ErrorKind::InvalidData,
+            PioSerialError::Overrun => embedded_io::ErrorKind::OutOfMemory,
+            PioSerialError::Break => embedded_io::ErrorKind::InvalidData,
+        }
     }
 }
 impl<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex> embedded_io::ErrorType
@@ -482,11 +1388,16 @@ impl<RXID: PinId, TXID: PinId, PIO: PIOExt> embedded_io::ErrorType
 {
     type Error = PioSerialError;
 }
+impl<TxID: PinId, DeID: PinId, PIO: PIOExt, SM: StateMachineIndex> embedded_io::ErrorType
+    for PioUartTxRs485<TxID, DeID, PIO, SM, pio::Running>
+{
+    type Error = PioSerialError;
+}
 impl<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex> embedded_io::Read
     for PioUartRx<PinID, PIO, SM, pio::Running>
 {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
-        self.read_raw(buf).map_err(|_| PioSerialError::IO)
+        self.read_raw(buf)
     }
 }
 impl<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex> embedded_io::Write
@@ -502,6 +1413,19 @@ impl<PinID: PinId, PIO: PIOExt, SM: StateMachineIndex> embedded_io::Write
         Ok(())
     }
 }
+impl<TxID: PinId, DeID: PinId, PIO: PIOExt, SM: StateMachineIndex> embedded_io::Write
+    for PioUartTxRs485<TxID, DeID, PIO, SM, pio::Running>
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.write_raw(buf)
+            .map(|_| buf.len())
+            .map_err(|_| PioSerialError::IO)
+    }
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.flush();
+        Ok(())
+    }
+}
 
 impl<RXID: PinId, TXID: PinId, PIO: PIOExt> embedded_io::Read
     for PioUart<RXID, TXID, PIO, pio::Running>
@@ -520,3 +1444,208 @@ impl<RXID: PinId, TXID: PinId, PIO: PIOExt> embedded_io::Write
         embedded_io::Write::flush(&mut self.tx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_bits_adds_one_for_parity() {
+        let frame_bits =
+            |data_bits, parity| PioUartConfig::new(data_bits, parity, StopBits::One).frame_bits();
+        assert_eq!(frame_bits(9, Parity::None), 9);
+        assert_eq!(frame_bits(8, Parity::Even), 9);
+        assert_eq!(frame_bits(5, Parity::Odd), 6);
+    }
+
+    #[test]
+    fn parity_bit_none_is_always_zero() {
+        assert_eq!(parity_bit(0b0000_0000, Parity::None), 0);
+        assert_eq!(parity_bit(0b1111_1111, Parity::None), 0);
+    }
+
+    #[test]
+    fn parity_bit_even_matches_bit_count() {
+        assert_eq!(parity_bit(0b0000_0000, Parity::Even), 0);
+        assert_eq!(parity_bit(0b0000_0001, Parity::Even), 1);
+        assert_eq!(parity_bit(0b0000_0011, Parity::Even), 0);
+        assert_eq!(parity_bit(0b0000_0111, Parity::Even), 1);
+    }
+
+    #[test]
+    fn parity_bit_odd_is_inverse_of_even() {
+        for data in 0u16..=0xFF {
+            assert_eq!(
+                parity_bit(data, Parity::Odd),
+                parity_bit(data, Parity::Even) ^ 1
+            );
+        }
+    }
+
+    #[test]
+    fn address_filter_matches_masked_address_only() {
+        let filter = AddressFilter {
+            addr: 0x05,
+            mask: 0x0F,
+            matched: false,
+        };
+        assert!(filter.matches(0x05));
+        assert!(filter.matches(0x15)); // high nibble ignored by mask
+        assert!(!filter.matches(0x06));
+    }
+
+    /// A fake [`RxFifo`] driven by a fixed list of `(raw_frame, line_error)`
+    /// pairs, so [`drain_words`]'s error-deferral logic can be exercised
+    /// without real PIO hardware. `still_full` answers `is_full` once the
+    /// list is exhausted, simulating a further frame arriving on the wire
+    /// while `buf` was draining.
+    struct FakeFifo<'a> {
+        frames: &'a [(u32, bool)],
+        pos: usize,
+        still_full: bool,
+    }
+
+    impl RxFifo for FakeFifo<'_> {
+        fn read(&mut self) -> Option<u32> {
+            let (raw, _) = *self.frames.get(self.pos)?;
+            self.pos += 1;
+            Some(raw)
+        }
+        fn is_full(&self) -> bool {
+            self.still_full
+        }
+        fn take_line_error(&mut self) -> bool {
+            self.frames[self.pos - 1].1
+        }
+    }
+
+    /// Mirrors [`PioUartRx::read_words`]'s pending-error deferral on top of
+    /// [`drain_words`], so tests can call it twice in a row the way a real
+    /// caller would.
+    fn read_words(
+        fifo: &mut FakeFifo,
+        pending_error: &mut Option<PioSerialError>,
+        frame_bits: u8,
+        data_bits: u8,
+        parity: Parity,
+        buf: &mut [u16],
+    ) -> Result<usize, PioSerialError> {
+        if let Some(err) = pending_error.take() {
+            return Err(err);
+        }
+        let (n, err) = drain_words(fifo, frame_bits, data_bits, parity, &mut None, buf);
+        *pending_error = err;
+        Ok(n)
+    }
+
+    #[test]
+    fn read_words_defers_overrun_until_after_good_prefix() {
+        let mut fifo = FakeFifo {
+            frames: &[(0x001 << 23, false), (0x002 << 23, false)],
+            pos: 0,
+            still_full: true, // a further frame is already waiting once buf fills up
+        };
+        let mut pending_error = None;
+        let mut buf = [0u16; 2];
+
+        assert_eq!(
+            read_words(&mut fifo, &mut pending_error, 9, 9, Parity::None, &mut buf),
+            Ok(2)
+        );
+        assert_eq!(buf, [0x001, 0x002]);
+
+        assert_eq!(
+            read_words(&mut fifo, &mut pending_error, 9, 9, Parity::None, &mut buf),
+            Err(PioSerialError::Overrun)
+        );
+    }
+
+    #[test]
+    fn read_words_defers_framing_until_after_good_prefix() {
+        let mut fifo = FakeFifo {
+            frames: &[(0x001 << 23, false), (0x055 << 23, true)],
+            pos: 0,
+            still_full: false,
+        };
+        let mut pending_error = None;
+        let mut buf = [0u16; 4];
+
+        assert_eq!(
+            read_words(&mut fifo, &mut pending_error, 9, 9, Parity::None, &mut buf),
+            Ok(1)
+        );
+        assert_eq!(buf[0], 0x001);
+
+        assert_eq!(
+            read_words(&mut fifo, &mut pending_error, 9, 9, Parity::None, &mut buf),
+            Err(PioSerialError::Framing)
+        );
+    }
+
+    #[test]
+    fn read_words_defers_break_until_after_good_prefix() {
+        let mut fifo = FakeFifo {
+            frames: &[(0x001 << 23, false), (0x000 << 23, true)],
+            pos: 0,
+            still_full: false,
+        };
+        let mut pending_error = None;
+        let mut buf = [0u16; 4];
+
+        assert_eq!(
+            read_words(&mut fifo, &mut pending_error, 9, 9, Parity::None, &mut buf),
+            Ok(1)
+        );
+        assert_eq!(buf[0], 0x001);
+
+        assert_eq!(
+            read_words(&mut fifo, &mut pending_error, 9, 9, Parity::None, &mut buf),
+            Err(PioSerialError::Break)
+        );
+    }
+
+    #[test]
+    fn read_words_defers_parity_until_after_good_prefix() {
+        // frame_bits 9 = 8 data bits + 1 parity bit (bit 8). 0x003 has even
+        // parity, so a parity bit of 0 passes an Even check; 0x001 has odd
+        // parity, so the same parity bit of 0 fails it.
+        let mut fifo = FakeFifo {
+            frames: &[(0x003 << 23, false), (0x001 << 23, false)],
+            pos: 0,
+            still_full: false,
+        };
+        let mut pending_error = None;
+        let mut buf = [0u16; 4];
+
+        assert_eq!(
+            read_words(&mut fifo, &mut pending_error, 9, 8, Parity::Even, &mut buf),
+            Ok(1)
+        );
+        assert_eq!(buf[0], 0x003);
+
+        assert_eq!(
+            read_words(&mut fifo, &mut pending_error, 9, 8, Parity::Even, &mut buf),
+            Err(PioSerialError::Parity)
+        );
+    }
+
+    #[test]
+    fn read_words_strips_parity_bit_instead_of_returning_it_as_data() {
+        // 8 data bits + Even parity: data 0x01 has odd parity, so the sent
+        // parity bit (frame bit 8) is 1. The returned word must be the bare
+        // 0x01 data, not 0x101 with the parity bit leaked into bit 8.
+        let mut fifo = FakeFifo {
+            frames: &[(0x101 << 23, false)],
+            pos: 0,
+            still_full: false,
+        };
+        let mut pending_error = None;
+        let mut buf = [0u16; 1];
+
+        assert_eq!(
+            read_words(&mut fifo, &mut pending_error, 9, 8, Parity::Even, &mut buf),
+            Ok(1)
+        );
+        assert_eq!(buf[0], 0x001);
+    }
+}